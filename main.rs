@@ -1,4 +1,5 @@
 use std::error::Error;
+use std::fmt;
 use std::io;
 use std::fs::File;
 use std::io::Read;
@@ -11,129 +12,578 @@ use std::str::Chars;
 pub enum Token {
     // Identifiers and numbers are stored as strings and floats.
     Identifier(String),
-    NumberLiteral(f64),
+    Integer(i64, Radix),
+    Float(f64),
+    StringLiteral(String),
 
     Plus,
     Minus,
     Star,
+    StarStar,
     Slash,
     Caret,
     Equal,
+    EqualEqual,
+    Bang,
+    BangEqual,
+    Less,
+    LessEqual,
+    Greater,
+    GreaterEqual,
+    Arrow,
 
     SemiColon,
     LParen,
     RParen,
 
     Let,
+
+    // Marks the end of input; the iterator yields this exactly once.
+    Eof,
 }
 
-struct Lexer<'a> {
-    // Using Rust's internal Peekable iterator 
-    input_iter: Peekable<Chars<'a>>,
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Radix {
+    // Tags which prefix (if any) an Integer literal was written with.
+    Decimal,
+    Hex,
+    Octal,
+    Binary,
 }
 
-impl<'a> Lexer<'a> {
-    fn new(input: &'a String) -> Lexer<'a> {
-        Lexer { input_iter: input.chars().peekable() }
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Posn {
+    // Line and column are 1-indexed, offset is a 0-indexed byte offset into the source.
+    pub line: usize,
+    pub col: usize,
+    pub offset: usize,
+}
+
+impl Posn {
+    fn start() -> Posn {
+        Posn { line: 1, col: 1, offset: 0 }
     }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span {
+    // The lexeme occupies [start, end) of the source.
+    pub start: Posn,
+    pub end: Posn,
+}
+
+#[derive(Debug)]
+pub enum LexError {
+    // A number literal that failed to parse, e.g. "1.2.3".
+    InvalidNumber { text: String, span: Span },
+    // A character that doesn't start any known token.
+    UnexpectedChar { ch: char, span: Span },
+    // A `/* ...` with no matching `*/` before EOF.
+    UnterminatedComment { span: Span },
+    // A `"...` with no closing quote before EOF.
+    UnterminatedString { span: Span },
+    // An escape sequence unescape() doesn't recognize, e.g. "\q".
+    InvalidEscape { text: String, span: Span },
+}
 
-    fn read_char(&mut self) -> Option<char> {
-        // Advances the iterator and returns the next value
-        self.input_iter.next()
+impl fmt::Display for LexError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LexError::InvalidNumber { text, span } => {
+                write!(f, "invalid number literal {:?} at {:?}", text, span.start)
+            }
+            LexError::UnexpectedChar { ch, span } => {
+                write!(f, "unexpected character {:?} at {:?}", ch, span.start)
+            }
+            LexError::UnterminatedComment { span } => {
+                write!(f, "unterminated block comment starting at {:?}", span.start)
+            }
+            LexError::UnterminatedString { span } => {
+                write!(f, "unterminated string literal starting at {:?}", span.start)
+            }
+            LexError::InvalidEscape { text, span } => {
+                write!(f, "invalid escape sequence {:?} at {:?}", text, span.start)
+            }
+        }
     }
+}
+
+impl Error for LexError {}
+
+// Decodes the escape sequences in a string literal's raw contents (the text between, but not
+// including, the surrounding quotes). Kept separate from the token-materializing code in Lexer
+// so it can be tested and reused on its own, e.g. for char literals later on.
+fn unescape(raw: &str, span: Span) -> Result<String, LexError> {
+    let mut out = String::new();
+    let mut chars = raw.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => out.push('\n'),
+            Some('t') => out.push('\t'),
+            Some('r') => out.push('\r'),
+            Some('\\') => out.push('\\'),
+            Some('"') => out.push('"'),
+            Some('0') => out.push('\0'),
+            Some('u') => {
+                if chars.next() != Some('{') {
+                    return Err(LexError::InvalidEscape { text: "\\u".to_string(), span });
+                }
+                let mut hex = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(h) => hex.push(h),
+                        None => {
+                            return Err(LexError::InvalidEscape {
+                                text: format!("\\u{{{}", hex),
+                                span,
+                            })
+                        }
+                    }
+                }
+                let invalid = || LexError::InvalidEscape { text: format!("\\u{{{}}}", hex), span };
+                let code = u32::from_str_radix(&hex, 16).map_err(|_| invalid())?;
+                out.push(char::from_u32(code).ok_or_else(invalid)?);
+            }
+            Some(other) => {
+                return Err(LexError::InvalidEscape { text: format!("\\{}", other), span })
+            }
+            None => return Err(LexError::UnterminatedString { span }),
+        }
+    }
+    Ok(out)
+}
+
+// The low-level tag for a raw lexeme: what kind of thing is here, with no attached payload.
+// Pairing a `TokenKind` with a byte length over the original source is enough for a consumer
+// (a formatter, a syntax highlighter) that wants tokens without owning copies of the text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenKind {
+    Whitespace,
+    LineComment,
+    BlockComment,
+
+    Identifier,
+    Integer(Radix),
+    Float,
+    StringLiteral,
+
+    Plus,
+    Minus,
+    Star,
+    StarStar,
+    Slash,
+    Caret,
+    Equal,
+    EqualEqual,
+    Bang,
+    BangEqual,
+    Less,
+    LessEqual,
+    Greater,
+    GreaterEqual,
+    Arrow,
+
+    SemiColon,
+    LParen,
+    RParen,
+
+    Eof,
+
+    // Malformed input is still tagged and sized rather than halting the raw pass; the upper
+    // layer decides what diagnostic to raise.
+    Error(RawError),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RawError {
+    InvalidNumber,
+    UnexpectedChar,
+    UnterminatedComment,
+    UnterminatedString,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RawToken {
+    pub kind: TokenKind,
+    // Byte length of the lexeme in the original source, starting at the caller's running offset.
+    pub len: usize,
+}
+
+// Allocation-light lexing layer: walks the source char by char and reports where each lexeme
+// ends, without building Strings or attaching spans/diagnostics. `Lexer` slices the original
+// input using these lengths to materialize real `Token`s.
+struct RawLexer<'a> {
+    chars: Peekable<Chars<'a>>,
+    eof_sent: bool,
+}
 
-    fn peek_char(&mut self) -> Option<&char> {
-        // Returns a reference to the next() value without advancing the iterator
-        self.input_iter.peek()
+impl<'a> RawLexer<'a> {
+    fn new(input: &'a str) -> RawLexer<'a> {
+        RawLexer { chars: input.chars().peekable(), eof_sent: false }
     }
 
-    fn is_letter(c: char) -> bool {
-        // Check whether a letter is an actual letter or '_' or a digit
-        c.is_alphabetic() || c == '_'
+    fn bump(&mut self) -> Option<char> {
+        self.chars.next()
     }
 
-    fn lookup_keyword(key: String) -> Token {
-        // Match identifiers and keyword to appropriate token
-        match key.as_str() {
-            "let" => Token::Let,
-            _ => Token::Identifier(key),
-        }
+    fn peek(&mut self) -> Option<char> {
+        self.chars.peek().copied()
     }
 
-    fn skip_whitespace(&mut self) {
-        // Skip all whitespace characters
-        while let Some(&c) = self.peek_char() {
-            // Return reference to the next() value and evaluate
-            if c.is_whitespace() {
-                // If c is a whitespace characters, continue reading
-                let _ = self.read_char();
+    fn eat_while(&mut self, mut pred: impl FnMut(char) -> bool) -> usize {
+        let mut len = 0;
+        while let Some(c) = self.peek() {
+            if pred(c) {
+                self.bump();
+                len += c.len_utf8();
             } else {
                 break;
             }
         }
+        len
     }
 
-    fn read_identifier(&mut self, c: char) -> String {
-        // Create a new string to contain all possible characters
-        let mut identifiers = String::new();
-        identifiers.push(c);
-        while let Some(&c) = self.peek_char() {
-            // Return reference to the next() value and evaluate
-            if c.is_alphanumeric() || c == '_' {
-                // Check whether c is a letter or a digit or '_'
-                identifiers.push(self.read_char().unwrap());
-            } else {
-                // None of the above, break
-                break;
+    fn if_peek_eq(&mut self, expected: char, two: TokenKind, one: TokenKind, len: &mut usize) -> TokenKind {
+        // Consult peek to decide between a one- and two-character token.
+        if self.peek() == Some(expected) {
+            self.bump();
+            *len += expected.len_utf8();
+            two
+        } else {
+            one
+        }
+    }
+
+    fn raw_identifier(&mut self, first: char) -> RawToken {
+        let len = first.len_utf8() + self.eat_while(|c| c.is_alphanumeric() || c == '_');
+        RawToken { kind: TokenKind::Identifier, len }
+    }
+
+    fn raw_radix_number(&mut self, tag: Radix, radix: u32) -> RawToken {
+        // The leading '0' and the 'x'/'o'/'b' tag char were already consumed by the caller.
+        let digits = self.eat_while(|c| c.is_digit(radix));
+        let kind = if digits == 0 { TokenKind::Error(RawError::InvalidNumber) } else { TokenKind::Integer(tag) };
+        RawToken { kind, len: 2 + digits }
+    }
+
+    fn raw_number(&mut self, first: char) -> RawToken {
+        if first == '0' {
+            match self.peek() {
+                Some('x') => {
+                    self.bump();
+                    return self.raw_radix_number(Radix::Hex, 16);
+                }
+                Some('o') => {
+                    self.bump();
+                    return self.raw_radix_number(Radix::Octal, 8);
+                }
+                Some('b') => {
+                    self.bump();
+                    return self.raw_radix_number(Radix::Binary, 2);
+                }
+                _ => {}
             }
+        }
+
+        let mut len = first.len_utf8();
+        let mut has_dot = false;
+        let mut has_exp = false;
+        let mut malformed = false;
+        loop {
+            match self.peek() {
+                Some(c) if c.is_ascii_digit() => {
+                    self.bump();
+                    len += c.len_utf8();
+                }
+                Some('.') => {
+                    self.bump();
+                    len += 1;
+                    if has_dot || has_exp {
+                        // A second '.' (or one inside the exponent) makes this not a number.
+                        malformed = true;
+                        break;
+                    }
+                    has_dot = true;
+                }
+                Some(c) if (c == 'e' || c == 'E') && !has_exp => {
+                    self.bump();
+                    len += c.len_utf8();
+                    has_exp = true;
+                    if let Some(sign) = self.peek() {
+                        if sign == '+' || sign == '-' {
+                            self.bump();
+                            len += sign.len_utf8();
+                        }
+                    }
+                    let exp_digits = self.eat_while(|c| c.is_ascii_digit());
+                    len += exp_digits;
+                    if exp_digits == 0 {
+                        malformed = true;
+                        break;
+                    }
+                }
+                _ => break,
             }
-        return identifiers
-    }
-
-    fn read_number(&mut self, c: char) -> f64 {
-        // Create a new string to contain all possible characters
-        let mut number = String::new();
-        number.push(c);
-        while let Some(&c) = self.peek_char() {
-            // Return a reference to the next element and evaluate
-            if c.is_digit(10) || c == '.' {
-                // If c is a digit or '.', add to string
-                number.push(self.read_char().unwrap());
-            } else {
-                // None of the above, break
+        }
+
+        let kind = if malformed {
+            TokenKind::Error(RawError::InvalidNumber)
+        } else if has_dot || has_exp {
+            TokenKind::Float
+        } else {
+            TokenKind::Integer(Radix::Decimal)
+        };
+        RawToken { kind, len }
+    }
+
+    fn raw_string(&mut self) -> RawToken {
+        // The opening '"' was already consumed.
+        let mut len = 1;
+        loop {
+            match self.bump() {
+                Some('"') => {
+                    len += 1;
+                    return RawToken { kind: TokenKind::StringLiteral, len };
+                }
+                Some('\\') => {
+                    len += 1;
+                    match self.bump() {
+                        Some(c) => len += c.len_utf8(),
+                        None => return RawToken { kind: TokenKind::Error(RawError::UnterminatedString), len },
+                    }
+                }
+                Some(c) => len += c.len_utf8(),
+                None => return RawToken { kind: TokenKind::Error(RawError::UnterminatedString), len },
+            }
+        }
+    }
+
+    fn raw_line_comment(&mut self) -> RawToken {
+        // The leading "//" was already consumed.
+        let len = 2 + self.eat_while(|c| c != '\n');
+        RawToken { kind: TokenKind::LineComment, len }
+    }
+
+    fn raw_block_comment(&mut self) -> RawToken {
+        // The leading "/*" was already consumed.
+        let mut len = 2;
+        loop {
+            match self.bump() {
+                Some('*') if self.peek() == Some('/') => {
+                    self.bump();
+                    len += 2;
+                    return RawToken { kind: TokenKind::BlockComment, len };
+                }
+                Some(c) => len += c.len_utf8(),
+                None => return RawToken { kind: TokenKind::Error(RawError::UnterminatedComment), len },
+            }
+        }
+    }
+
+    // Produces the next raw lexeme, or None once a single Eof token has already been handed out.
+    fn next_raw(&mut self) -> Option<RawToken> {
+        let c = match self.peek() {
+            Some(c) => c,
+            None => {
+                if self.eof_sent {
+                    return None;
+                }
+                self.eof_sent = true;
+                return Some(RawToken { kind: TokenKind::Eof, len: 0 });
+            }
+        };
+
+        if c.is_whitespace() {
+            let len = self.eat_while(|c| c.is_whitespace());
+            return Some(RawToken { kind: TokenKind::Whitespace, len });
+        }
+
+        self.bump();
+
+        if c == '/' {
+            return Some(match self.peek() {
+                Some('/') => {
+                    self.bump();
+                    self.raw_line_comment()
+                }
+                Some('*') => {
+                    self.bump();
+                    self.raw_block_comment()
+                }
+                _ => RawToken { kind: TokenKind::Slash, len: 1 },
+            });
+        }
+        if c == '"' {
+            return Some(self.raw_string());
+        }
+        if c.is_alphabetic() || c == '_' {
+            return Some(self.raw_identifier(c));
+        }
+        if c.is_ascii_digit() {
+            return Some(self.raw_number(c));
+        }
+
+        let mut len = c.len_utf8();
+        let kind = match c {
+            '=' => self.if_peek_eq('=', TokenKind::EqualEqual, TokenKind::Equal, &mut len),
+            '!' => self.if_peek_eq('=', TokenKind::BangEqual, TokenKind::Bang, &mut len),
+            '<' => self.if_peek_eq('=', TokenKind::LessEqual, TokenKind::Less, &mut len),
+            '>' => self.if_peek_eq('=', TokenKind::GreaterEqual, TokenKind::Greater, &mut len),
+            '+' => TokenKind::Plus,
+            '-' => self.if_peek_eq('>', TokenKind::Arrow, TokenKind::Minus, &mut len),
+            '*' => self.if_peek_eq('*', TokenKind::StarStar, TokenKind::Star, &mut len),
+            '^' => TokenKind::Caret,
+            ';' => TokenKind::SemiColon,
+            '(' => TokenKind::LParen,
+            ')' => TokenKind::RParen,
+            _ => TokenKind::Error(RawError::UnexpectedChar),
+        };
+        Some(RawToken { kind, len })
+    }
+}
+
+struct Lexer<'a> {
+    // The full source; raw lexemes are byte-sliced out of this rather than rebuilt char by char.
+    input: &'a str,
+    raw: RawLexer<'a>,
+    // Current position, advanced past each lexeme (including skipped trivia) as it's sliced.
+    pos: Posn,
+}
+
+impl<'a> Lexer<'a> {
+    fn new(input: &'a String) -> Lexer<'a> {
+        Lexer { input, raw: RawLexer::new(input), pos: Posn::start() }
+    }
+
+    // Drains the lexer, collecting every token up to and including Eof. Library-style
+    // convenience API: the CLI binary streams tokens via next_token() instead, so this isn't
+    // called from main() itself.
+    #[allow(dead_code)]
+    pub fn lex(&mut self) -> Result<Vec<Token>, LexError> {
+        let mut tokens = Vec::new();
+        for token in self {
+            let token = token?;
+            let is_eof = matches!(token, Token::Eof);
+            tokens.push(token);
+            if is_eof {
                 break;
             }
         }
-        // Convert the string to appropriate type
-        return number.parse::<f64>().unwrap()
-    }
-
-    pub fn next_token(&mut self) -> Option<Token> {
-        self.skip_whitespace();
-        if let Some(c) = self.read_char() {
-            match c {
-                '=' => Some(Token::Equal),
-                '+' => Some(Token::Plus),
-                '-' => Some(Token::Minus),
-                '*' => Some(Token::Star),
-                '/' => Some(Token::Slash),
-                '^' => Some(Token::Caret),
-                ';' => Some(Token::SemiColon),
-                '(' => Some(Token::LParen),
-                ')' => Some(Token::RParen),
-                _ => {
-                    if Self::is_letter(c) {
-                        // Process c in read_identifier() --> process results in lookup_keyword() to find appropriate token
-                        Some(Self::lookup_keyword(self.read_identifier(c)))
-                    } else if c.is_digit(10) {
-                        Some(Token::NumberLiteral(self.read_number(c)))
-                    } else {
-                        None
+        Ok(tokens)
+    }
+
+    fn lookup_keyword(key: &str) -> Token {
+        // Match identifiers and keyword to appropriate token
+        match key {
+            "let" => Token::Let,
+            _ => Token::Identifier(key.to_string()),
+        }
+    }
+
+    // Advances self.pos over a lexeme's text, which may itself contain newlines (a block
+    // comment or a multi-line string).
+    fn advance_pos(&mut self, text: &str) {
+        for c in text.chars() {
+            if c == '\n' {
+                self.pos.line += 1;
+                self.pos.col = 1;
+            } else {
+                self.pos.col += 1;
+            }
+        }
+        self.pos.offset += text.len();
+    }
+
+    pub fn next_token(&mut self) -> Result<Option<(Token, Span)>, LexError> {
+        loop {
+            let raw = match self.raw.next_raw() {
+                Some(raw) => raw,
+                None => return Ok(None),
+            };
+
+            let start = self.pos;
+            let text = &self.input[start.offset..start.offset + raw.len];
+            self.advance_pos(text);
+            let span = Span { start, end: self.pos };
+
+            let token = match raw.kind {
+                TokenKind::Whitespace | TokenKind::LineComment | TokenKind::BlockComment => continue,
+
+                TokenKind::Error(RawError::UnexpectedChar) => {
+                    return Err(LexError::UnexpectedChar { ch: text.chars().next().unwrap(), span })
+                }
+                TokenKind::Error(RawError::UnterminatedComment) => {
+                    return Err(LexError::UnterminatedComment { span })
+                }
+                TokenKind::Error(RawError::UnterminatedString) => {
+                    return Err(LexError::UnterminatedString { span })
+                }
+                TokenKind::Error(RawError::InvalidNumber) => {
+                    return Err(LexError::InvalidNumber { text: text.to_string(), span })
+                }
+
+                TokenKind::Identifier => Self::lookup_keyword(text),
+                TokenKind::Integer(radix) => {
+                    let parsed = match radix {
+                        Radix::Decimal => text.parse::<i64>(),
+                        Radix::Hex => i64::from_str_radix(&text[2..], 16),
+                        Radix::Octal => i64::from_str_radix(&text[2..], 8),
+                        Radix::Binary => i64::from_str_radix(&text[2..], 2),
+                    };
+                    match parsed {
+                        Ok(n) => Token::Integer(n, radix),
+                        Err(_) => {
+                            return Err(LexError::InvalidNumber { text: text.to_string(), span })
+                        }
                     }
                 }
-            }
-        } else {
-            None
+                TokenKind::Float => match text.parse::<f64>() {
+                    Ok(n) => Token::Float(n),
+                    Err(_) => return Err(LexError::InvalidNumber { text: text.to_string(), span }),
+                },
+                TokenKind::StringLiteral => {
+                    let inner = &text[1..text.len() - 1];
+                    Token::StringLiteral(unescape(inner, span)?)
+                }
+
+                TokenKind::Plus => Token::Plus,
+                TokenKind::Minus => Token::Minus,
+                TokenKind::Star => Token::Star,
+                TokenKind::StarStar => Token::StarStar,
+                TokenKind::Slash => Token::Slash,
+                TokenKind::Caret => Token::Caret,
+                TokenKind::Equal => Token::Equal,
+                TokenKind::EqualEqual => Token::EqualEqual,
+                TokenKind::Bang => Token::Bang,
+                TokenKind::BangEqual => Token::BangEqual,
+                TokenKind::Less => Token::Less,
+                TokenKind::LessEqual => Token::LessEqual,
+                TokenKind::Greater => Token::Greater,
+                TokenKind::GreaterEqual => Token::GreaterEqual,
+                TokenKind::Arrow => Token::Arrow,
+                TokenKind::SemiColon => Token::SemiColon,
+                TokenKind::LParen => Token::LParen,
+                TokenKind::RParen => Token::RParen,
+                TokenKind::Eof => Token::Eof,
+            };
+            return Ok(Some((token, span)));
+        }
+    }
+}
+
+impl<'a> Iterator for Lexer<'a> {
+    type Item = Result<Token, LexError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.next_token() {
+            Ok(Some((token, _span))) => Some(Ok(token)),
+            Ok(None) => None,
+            Err(err) => Some(Err(err)),
         }
     }
 }
@@ -150,14 +600,109 @@ fn main() -> Result<(), Box<dyn Error>> {
     let mut file_contents = String::new();
     file.read_to_string(&mut file_contents)?;
 
-    // Loop through tokens
+    // Loop through tokens, printing each one alongside where it occurred in the source.
     let mut lexer = Lexer::new(&file_contents);
 
-    loop {
-        match lexer.next_token() {
-            Some(token_type) => println!("{:?}", token_type),
-            None => break,
-        }
+    while let Some((token, span)) = lexer.next_token()? {
+        println!("{:?} @ {:?}", token, span);
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lex_drains_to_eof() {
+        let src = "let x = 1;".to_string();
+        let mut lexer = Lexer::new(&src);
+        let tokens = lexer.lex().unwrap();
+        assert!(matches!(tokens.last(), Some(Token::Eof)));
+        assert_eq!(tokens.len(), 6); // let, x, =, 1, ;, Eof
+    }
+
+    // The exact Posn values don't matter to these tests, only which LexError variant (if any)
+    // unescape() produces.
+    fn dummy_span() -> Span {
+        Span { start: Posn::start(), end: Posn::start() }
+    }
+
+    #[test]
+    fn unescape_decodes_known_escapes() {
+        let out = unescape(r#"a\nb\tc\rd\\e\"f\0"#, dummy_span()).unwrap();
+        assert_eq!(out, "a\nb\tc\rd\\e\"f\0");
+    }
+
+    #[test]
+    fn unescape_decodes_unicode_escape() {
+        let out = unescape(r"\u{1F600}", dummy_span()).unwrap();
+        assert_eq!(out, "\u{1F600}");
+    }
+
+    #[test]
+    fn unescape_rejects_unknown_escape() {
+        let err = unescape(r"\q", dummy_span()).unwrap_err();
+        assert!(matches!(err, LexError::InvalidEscape { text, .. } if text == "\\q"));
+    }
+
+    #[test]
+    fn unescape_rejects_unterminated_escape() {
+        let err = unescape("\\", dummy_span()).unwrap_err();
+        assert!(matches!(err, LexError::UnterminatedString { .. }));
+    }
+
+    // Lexes exactly one token from `src`, for asserting on a single number literal's shape.
+    fn lex_one(src: &str) -> Result<Token, LexError> {
+        let src = src.to_string();
+        let mut lexer = Lexer::new(&src);
+        Ok(lexer.next_token()?.expect("expected one token before Eof").0)
+    }
+
+    #[test]
+    fn number_with_second_dot_is_invalid() {
+        let err = lex_one("1.2.3").unwrap_err();
+        assert!(matches!(err, LexError::InvalidNumber { .. }));
+    }
+
+    #[test]
+    fn spans_track_line_and_column_across_newlines() {
+        let src = "let\nx = 1;".to_string();
+        let mut lexer = Lexer::new(&src);
+
+        let (token, span) = lexer.next_token().unwrap().unwrap();
+        assert!(matches!(token, Token::Let));
+        assert_eq!(span.start, Posn { line: 1, col: 1, offset: 0 });
+        assert_eq!(span.end, Posn { line: 1, col: 4, offset: 3 });
+
+        // "x" starts on the second line, right after the '\n'.
+        let (token, span) = lexer.next_token().unwrap().unwrap();
+        assert!(matches!(token, Token::Identifier(ref s) if s == "x"));
+        assert_eq!(span.start, Posn { line: 2, col: 1, offset: 4 });
+        assert_eq!(span.end, Posn { line: 2, col: 2, offset: 5 });
+    }
+
+    #[test]
+    fn hex_prefix_with_no_digits_is_invalid() {
+        let err = lex_one("0x").unwrap_err();
+        assert!(matches!(err, LexError::InvalidNumber { .. }));
+    }
+
+    #[test]
+    fn exponent_with_no_digits_is_invalid() {
+        let err = lex_one("1e").unwrap_err();
+        assert!(matches!(err, LexError::InvalidNumber { .. }));
+    }
+
+    #[test]
+    fn signed_exponent_with_no_digits_is_invalid() {
+        let err = lex_one("1.2e+").unwrap_err();
+        assert!(matches!(err, LexError::InvalidNumber { .. }));
+    }
+
+    #[test]
+    fn well_formed_numbers_still_parse() {
+        assert!(matches!(lex_one("0x1A").unwrap(), Token::Integer(26, Radix::Hex)));
+        assert!(matches!(lex_one("3.14e-2").unwrap(), Token::Float(f) if (f - 0.0314).abs() < 1e-9));
+    }
+}